@@ -0,0 +1,219 @@
+//! Async counterpart to [`crate::client`], enabled via the `async` feature
+//!
+//! Mirrors the sync surface in [`crate::client`] — the same [`Schema`]/[`Endpoint`] types, the
+//! same [`EmbedRequest`]/[`RetryPolicy`] parameters, and the same scheme-matching and
+//! response-parsing code — so a consumer that needs non-blocking I/O can `join!` many oEmbed
+//! lookups instead of spawning blocking threads.
+
+use crate::client::{EmbedRequest, RetryPolicy, Schema};
+use crate::{Endpoint, Error, Format, Provider, Response, Result};
+use std::borrow::Cow;
+
+/// Async counterpart to [`crate::client::Http`]
+#[async_trait::async_trait]
+pub trait AsyncHttp {
+    /// URL-encode a string so it can be used safely as part of a URL.
+    async fn url_encode<'a>(&mut self, s: &'a str) -> crate::client::HttpResult<Cow<'a, str>>;
+
+    /// Retrieve the body of a resource located at `url`.
+    async fn get(&mut self, url: &str) -> crate::client::HttpResult<String>;
+
+    /// Whether a failed [`get`][1] call should be retried under a [`RetryPolicy`]
+    ///
+    /// The default implementation treats nothing as retryable. Override this to opt errors your
+    /// implementation considers transient (timeouts, 5xx status codes, ...) into automatic retry.
+    ///
+    /// [1]: AsyncHttp::get
+    fn is_retryable(&self, _err: &(dyn std::error::Error + 'static)) -> bool {
+        false
+    }
+}
+
+/// Whether a failed call should be retried, mirroring [`RetryPolicy`]'s sync-side check
+fn is_retryable(policy: &RetryPolicy, http: &impl AsyncHttp, err: &(dyn std::error::Error + 'static)) -> bool {
+    if policy.treat_timeout_as_retryable {
+        if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+            if io_err.kind() == std::io::ErrorKind::TimedOut {
+                return true;
+            }
+        }
+    }
+
+    http.is_retryable(err)
+}
+
+/// Call [`AsyncHttp::get`], retrying transient failures according to `policy`
+async fn get_with_retry_async(
+    http: &mut impl AsyncHttp,
+    url: &str,
+    policy: &RetryPolicy,
+) -> crate::client::HttpResult<String> {
+    let mut attempt = 1;
+
+    loop {
+        match http.get(url).await {
+            Ok(body) => return Ok(body),
+            Err(err) if attempt < policy.max_attempts && is_retryable(policy, &*http, err.as_ref()) => {
+                let delay = policy.backoff_delay(attempt);
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+impl Schema {
+    /// Async counterpart to [`Schema::fetch_from_url`]
+    pub async fn fetch_from_url_async(http: &mut impl AsyncHttp, url: &str) -> Result<Self> {
+        Self::fetch_from_url_with_retry_async(http, url, &RetryPolicy::none()).await
+    }
+
+    /// Async counterpart to [`Schema::fetch_from_url_with_retry`]
+    pub async fn fetch_from_url_with_retry_async(
+        http: &mut impl AsyncHttp,
+        url: &str,
+        policy: &RetryPolicy,
+    ) -> Result<Self> {
+        let s = get_with_retry_async(http, url, policy)
+            .await
+            .map_err(Error::HttpGet)?;
+
+        let providers: Vec<Provider> = serde_json::from_str(&s).map_err(Error::ParseError)?;
+
+        Ok(Self::from_providers(providers))
+    }
+
+    /// Async counterpart to [`Schema::fetch`]
+    pub async fn fetch_async<'a>(
+        &self,
+        http: &mut impl AsyncHttp,
+        request: impl Into<EmbedRequest<'a>>,
+    ) -> Option<Result<Response>> {
+        self.fetch_with_retry_async(http, request, &RetryPolicy::none())
+            .await
+    }
+
+    /// Async counterpart to [`Schema::fetch_with_retry`]
+    pub async fn fetch_with_retry_async<'a>(
+        &self,
+        http: &mut impl AsyncHttp,
+        request: impl Into<EmbedRequest<'a>>,
+        policy: &RetryPolicy,
+    ) -> Option<Result<Response>> {
+        let request = request.into();
+
+        match self.match_endpoint(request.url) {
+            Some(m) => Some(m.endpoint.fetch_with_retry_async(http, request, policy).await),
+            None => None,
+        }
+    }
+}
+
+impl Endpoint {
+    /// Async counterpart to [`Endpoint::fetch`]
+    pub async fn fetch_async<'a>(
+        &self,
+        http: &mut impl AsyncHttp,
+        request: impl Into<EmbedRequest<'a>>,
+    ) -> Result<Response> {
+        self.fetch_with_retry_async(http, request, &RetryPolicy::none())
+            .await
+    }
+
+    /// Async counterpart to [`Endpoint::fetch_with_retry`]
+    pub async fn fetch_with_retry_async<'a>(
+        &self,
+        http: &mut impl AsyncHttp,
+        request: impl Into<EmbedRequest<'a>>,
+        policy: &RetryPolicy,
+    ) -> Result<Response> {
+        let request = request.into();
+        let format = self.preferred_format();
+
+        let encoded_url = http
+            .url_encode(request.url)
+            .await
+            .map_err(Error::HttpUrlEncode)?;
+        let mut request_url = format!(
+            "{}?format={}&url={}",
+            self.url,
+            format.as_query_param(),
+            encoded_url
+        );
+        request.append_query_params(&mut request_url);
+
+        let s = get_with_retry_async(http, &request_url, policy)
+            .await
+            .map_err(Error::HttpGet)?;
+
+        match format {
+            Format::Json => serde_json::from_str(&s).map_err(Error::ParseError),
+            Format::Xml => crate::xml::parse_response(&s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct AsyncFlakyHttp {
+        failures_remaining: Cell<u32>,
+    }
+
+    #[async_trait::async_trait]
+    impl AsyncHttp for AsyncFlakyHttp {
+        async fn url_encode<'a>(&mut self, s: &'a str) -> crate::client::HttpResult<Cow<'a, str>> {
+            Ok(s.into())
+        }
+
+        async fn get(&mut self, _url: &str) -> crate::client::HttpResult<String> {
+            if self.failures_remaining.get() > 0 {
+                self.failures_remaining.set(self.failures_remaining.get() - 1);
+                return Err("transient failure".into());
+            }
+
+            Ok("body".to_string())
+        }
+
+        fn is_retryable(&self, _err: &(dyn std::error::Error + 'static)) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn get_with_retry_async_succeeds_within_attempt_budget() {
+        let mut http = AsyncFlakyHttp {
+            failures_remaining: Cell::new(2),
+        };
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: std::time::Duration::ZERO,
+            treat_timeout_as_retryable: false,
+        };
+
+        assert_eq!(
+            get_with_retry_async(&mut http, "url", &policy).await.unwrap(),
+            "body"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_with_retry_async_gives_up_after_max_attempts() {
+        let mut http = AsyncFlakyHttp {
+            failures_remaining: Cell::new(5),
+        };
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            base_delay: std::time::Duration::ZERO,
+            treat_timeout_as_retryable: false,
+        };
+
+        assert!(get_with_retry_async(&mut http, "url", &policy).await.is_err());
+        assert_eq!(http.failures_remaining.get(), 3);
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{Endpoint, Error, Provider, Response, Result};
+use crate::{Endpoint, Error, Format, Provider, Response, Result};
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Result type for the [`Http`] trait
 pub type HttpResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -14,15 +15,177 @@ pub trait Http {
 
     /// Retrieve the body of a resource located at `url`.
     fn get(&mut self, url: &str) -> HttpResult<String>;
+
+    /// Whether a failed [`get`][1] call should be retried under a [`RetryPolicy`]
+    ///
+    /// The default implementation treats nothing as retryable. Override this to opt errors your
+    /// implementation considers transient (timeouts, 5xx status codes, ...) into automatic retry.
+    ///
+    /// [1]: Http::get
+    fn is_retryable(&self, _err: &(dyn std::error::Error + 'static)) -> bool {
+        false
+    }
+}
+
+/// Controls automatic retry of transient [`Http::get`] failures
+///
+/// Used by [`Schema::fetch_with_retry`], [`Schema::fetch_from_url_with_retry`], and
+/// [`Endpoint::fetch_with_retry`]. An error is retried when [`Http::is_retryable`] returns `true`
+/// for it, or when `treat_timeout_as_retryable` is set and the error is a
+/// [`std::io::Error`] with kind [`TimedOut`][1].
+///
+/// The delay between attempts doubles after each retry, starting from `base_delay` (exponential
+/// backoff), so a flaky endpoint isn't hammered at a fixed rate.
+///
+/// [1]: std::io::ErrorKind::TimedOut
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first. `1` disables retry.
+    pub max_attempts: u32,
+
+    /// Delay before the first retry. Doubles after each subsequent retry.
+    pub base_delay: std::time::Duration,
+
+    /// Whether a [`std::io::Error`] with kind [`TimedOut`][1] should be retried regardless of
+    /// [`Http::is_retryable`].
+    ///
+    /// [1]: std::io::ErrorKind::TimedOut
+    pub treat_timeout_as_retryable: bool,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries: a single attempt.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            base_delay: std::time::Duration::ZERO,
+            treat_timeout_as_retryable: false,
+        }
+    }
+
+    fn is_retryable<H: Http>(&self, http: &H, err: &(dyn std::error::Error + 'static)) -> bool {
+        if self.treat_timeout_as_retryable {
+            if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+                if io_err.kind() == std::io::ErrorKind::TimedOut {
+                    return true;
+                }
+            }
+        }
+
+        http.is_retryable(err)
+    }
+
+    /// The delay to wait before retry number `attempt` (`1` for the first retry, `2` for the
+    /// second, ...), doubling `base_delay` each time.
+    pub(crate) fn backoff_delay(&self, attempt: u32) -> std::time::Duration {
+        self.base_delay.saturating_mul(1u32 << (attempt - 1).min(31))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Call [`Http::get`], retrying transient failures according to `policy`
+///
+/// Returns as soon as a call succeeds, or once the final attempt's error is non-retryable or the
+/// attempt budget is exhausted.
+fn get_with_retry(http: &mut impl Http, url: &str, policy: &RetryPolicy) -> HttpResult<String> {
+    let mut attempt = 1;
+
+    loop {
+        match http.get(url) {
+            Ok(body) => return Ok(body),
+            Err(err) if attempt < policy.max_attempts && policy.is_retryable(&*http, err.as_ref()) => {
+                std::thread::sleep(policy.backoff_delay(attempt));
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// An oEmbed request, built from a target URL and the optional consumer parameters described in
+/// section 2.2 of the [oEmbed specification][1]
+///
+/// Built with [`EmbedRequest::new`], then optionally [`maxwidth`][2] / [`maxheight`][3]. Anything
+/// that can be turned [`Into<EmbedRequest>`] — including a bare `&str` — can be passed directly
+/// to [`Schema::fetch`] or [`Endpoint::fetch`], so the builder only needs to be constructed
+/// explicitly when a consumer parameter is actually set.
+///
+/// [1]: https://oembed.com/
+/// [2]: EmbedRequest::maxwidth
+/// [3]: EmbedRequest::maxheight
+#[derive(Clone, PartialEq, PartialOrd, Hash, Debug)]
+pub struct EmbedRequest<'a> {
+    pub(crate) url: &'a str,
+    maxwidth: Option<u32>,
+    maxheight: Option<u32>,
+}
+
+impl<'a> EmbedRequest<'a> {
+    /// Start building a request for `url`, with no consumer parameters set
+    pub fn new(url: &'a str) -> Self {
+        Self {
+            url,
+            maxwidth: None,
+            maxheight: None,
+        }
+    }
+
+    /// Request that the embedded resource be no wider than `maxwidth` pixels
+    pub fn maxwidth(mut self, maxwidth: u32) -> Self {
+        self.maxwidth = Some(maxwidth);
+        self
+    }
+
+    /// Request that the embedded resource be no taller than `maxheight` pixels
+    pub fn maxheight(mut self, maxheight: u32) -> Self {
+        self.maxheight = Some(maxheight);
+        self
+    }
+
+    /// Append this request's parameters to a `url=`-terminated query string
+    pub(crate) fn append_query_params(&self, query: &mut String) {
+        if let Some(maxwidth) = self.maxwidth {
+            query.push_str(&format!("&maxwidth={}", maxwidth));
+        }
+
+        if let Some(maxheight) = self.maxheight {
+            query.push_str(&format!("&maxheight={}", maxheight));
+        }
+    }
+}
+
+impl<'a> From<&'a str> for EmbedRequest<'a> {
+    fn from(url: &'a str) -> Self {
+        Self::new(url)
+    }
 }
 
 /// Schema containing known oEmbed providers and their endpoints
 ///
-/// The list of providers is currently quite small (~400 elements). For this reason, they
-/// are stored in a standard [`Vec`] and looked up with a linear scan.
-#[derive(Clone, PartialEq, PartialOrd, Hash, Debug, Default)]
+/// Endpoint schemes are indexed by host at load time, so [`match_endpoint`][1] only has to
+/// glob-match the handful of schemes registered under the request URL's host, plus a small
+/// fallback list of schemes that don't pin down a literal host (custom URI schemes like
+/// `spotify:*`, and schemes whose host itself contains a wildcard, like `http://*.flickr.com/*`).
+///
+/// [1]: Schema::match_endpoint
+#[derive(Clone, PartialEq, Debug, Default)]
 pub struct Schema {
     providers: Vec<Provider>,
+    host_index: HashMap<String, Vec<SchemeRef>>,
+    fallback_schemes: Vec<SchemeRef>,
+}
+
+/// Indices locating a single scheme string within [`Schema::providers`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct SchemeRef {
+    provider_idx: usize,
+    endpoint_idx: usize,
+    scheme_idx: usize,
 }
 
 /// Result of an endpoint search
@@ -57,7 +220,7 @@ impl Schema {
         let providers = serde_json::from_str(&json)
             .expect("Failed to load providers.json. This build of oembed is broken!");
 
-        Self { providers }
+        Self::from_providers(providers)
     }
 
     /// Load schema from the public list provided at `https://oembed.com/providers.json`
@@ -67,58 +230,205 @@ impl Schema {
 
     /// Load schema from a specific URL
     pub fn fetch_from_url(http: &mut impl Http, url: &str) -> Result<Self> {
-        let s = http.get(url).map_err(|e| Error::HttpGet(e.into()))?;
+        Self::fetch_from_url_with_retry(http, url, &RetryPolicy::none())
+    }
+
+    /// Load schema from a specific URL, retrying transient failures according to `policy`
+    pub fn fetch_from_url_with_retry(
+        http: &mut impl Http,
+        url: &str,
+        policy: &RetryPolicy,
+    ) -> Result<Self> {
+        let s = get_with_retry(http, url, policy).map_err(Error::HttpGet)?;
+
+        let providers = serde_json::from_str(&s).map_err(Error::ParseError)?;
+
+        Ok(Self::from_providers(providers))
+    }
+
+    /// Build a schema from a provider list, indexing endpoint schemes by host
+    pub(crate) fn from_providers(providers: Vec<Provider>) -> Self {
+        let mut host_index: HashMap<String, Vec<SchemeRef>> = HashMap::new();
+        let mut fallback_schemes = Vec::new();
+
+        for (provider_idx, provider) in providers.iter().enumerate() {
+            for (endpoint_idx, endpoint) in provider.endpoints.iter().enumerate() {
+                let Some(schemes) = &endpoint.schemes else {
+                    continue;
+                };
 
-        let providers = serde_json::from_str(&s).map_err(|e| Error::ParseError(e))?;
+                for (scheme_idx, scheme) in schemes.iter().enumerate() {
+                    let scheme_ref = SchemeRef {
+                        provider_idx,
+                        endpoint_idx,
+                        scheme_idx,
+                    };
 
-        Ok(Self { providers })
+                    match scheme_host(scheme) {
+                        Some(host) if !host.contains('*') => {
+                            host_index
+                                .entry(host.to_ascii_lowercase())
+                                .or_default()
+                                .push(scheme_ref);
+                        }
+                        _ => fallback_schemes.push(scheme_ref),
+                    }
+                }
+            }
+        }
+
+        Self {
+            providers,
+            host_index,
+            fallback_schemes,
+        }
     }
 
     /// Search for the first [`Endpoint`] with a scheme matching `url`
     pub fn match_endpoint(&self, url: &str) -> Option<MatchedEndpoint> {
-        for provider in &self.providers {
-            for endpoint in &provider.endpoints {
-                if let Some(matched_scheme) = endpoint.match_url_scheme(url) {
-                    return Some(MatchedEndpoint {
-                        provider,
-                        endpoint,
-                        matched_scheme,
-                    });
-                }
+        let mut candidates: Vec<&SchemeRef> = Vec::new();
+
+        if let Some(host) = scheme_host(url) {
+            if let Some(refs) = self.host_index.get(&host.to_ascii_lowercase()) {
+                candidates.extend(refs);
+            }
+        }
+        candidates.extend(&self.fallback_schemes);
+
+        candidates.sort_by_key(|r| (r.provider_idx, r.endpoint_idx, r.scheme_idx));
+
+        for scheme_ref in candidates {
+            let provider = &self.providers[scheme_ref.provider_idx];
+            let endpoint = &provider.endpoints[scheme_ref.endpoint_idx];
+            let scheme = &endpoint.schemes.as_ref().unwrap()[scheme_ref.scheme_idx];
+
+            if url_matches_scheme(url, scheme) {
+                return Some(MatchedEndpoint {
+                    provider,
+                    endpoint,
+                    matched_scheme: scheme,
+                });
             }
         }
 
         None
     }
 
-    /// Fetch an oEmbed response for `url`
+    /// Fetch an oEmbed response for `request`
+    ///
+    /// `request` is usually just a `&str` URL, but can be an [`EmbedRequest`] builder if
+    /// consumer parameters like `maxwidth`/`maxheight` are needed.
+    ///
+    /// Returns `None` if no endpoint with a scheme matching the request URL is found.
+    pub fn fetch<'a>(
+        &self,
+        http: &mut impl Http,
+        request: impl Into<EmbedRequest<'a>>,
+    ) -> Option<Result<Response>> {
+        self.fetch_with_retry(http, request, &RetryPolicy::none())
+    }
+
+    /// Fetch an oEmbed response for `request`, retrying transient failures according to `policy`
     ///
-    /// Returns `None` if no endpoint with a scheme matching `url` is found.
-    pub fn fetch(&self, http: &mut impl Http, url: &str) -> Option<Result<Response>> {
-        self.match_endpoint(url)
-            .map(|m| m.endpoint.fetch(http, url))
+    /// See [`Schema::fetch`] for details beyond retry behavior.
+    pub fn fetch_with_retry<'a>(
+        &self,
+        http: &mut impl Http,
+        request: impl Into<EmbedRequest<'a>>,
+        policy: &RetryPolicy,
+    ) -> Option<Result<Response>> {
+        let request = request.into();
+
+        self.match_endpoint(request.url)
+            .map(|m| m.endpoint.fetch_with_retry(http, request, policy))
     }
 }
 
 impl Endpoint {
-    /// Fetch an oEmbed response for `url` from this endpoint
-    pub fn fetch(&self, http: &mut impl Http, url: &str) -> Result<Response> {
-        let encoded_url = http.url_encode(url).map_err(|e| Error::HttpUrlEncode(e))?;
-        let request_url = format!("{}?format=json&url={}", self.url, encoded_url);
+    /// Fetch an oEmbed response for `request` from this endpoint
+    ///
+    /// `request` is usually just a `&str` URL, but can be an [`EmbedRequest`] builder if
+    /// consumer parameters like `maxwidth`/`maxheight` are needed.
+    ///
+    /// Picks a response format this endpoint advertises via [`Endpoint::formats`][1], preferring
+    /// JSON over XML if both are supported.
+    ///
+    /// [1]: Endpoint::formats
+    pub fn fetch<'a>(
+        &self,
+        http: &mut impl Http,
+        request: impl Into<EmbedRequest<'a>>,
+    ) -> Result<Response> {
+        self.fetch_with_retry(http, request, &RetryPolicy::none())
+    }
 
-        let s = http.get(&request_url).map_err(|e| Error::HttpGet(e))?;
+    /// Fetch an oEmbed response for `request` from this endpoint, retrying transient failures
+    /// according to `policy`
+    ///
+    /// See [`Endpoint::fetch`] for details beyond retry behavior.
+    pub fn fetch_with_retry<'a>(
+        &self,
+        http: &mut impl Http,
+        request: impl Into<EmbedRequest<'a>>,
+        policy: &RetryPolicy,
+    ) -> Result<Response> {
+        let request = request.into();
+        let format = self.preferred_format();
+
+        let encoded_url = http
+            .url_encode(request.url)
+            .map_err(|e| Error::HttpUrlEncode(e))?;
+        let mut request_url = format!(
+            "{}?format={}&url={}",
+            self.url,
+            format.as_query_param(),
+            encoded_url
+        );
+        request.append_query_params(&mut request_url);
 
-        serde_json::from_str(&s).map_err(|e| Error::ParseError(e))
+        let s = get_with_retry(http, &request_url, policy).map_err(Error::HttpGet)?;
+
+        match format {
+            Format::Json => serde_json::from_str(&s).map_err(Error::ParseError),
+            Format::Xml => crate::xml::parse_response(&s),
+        }
     }
 
-    fn match_url_scheme(&self, url: &str) -> Option<&str> {
-        self.schemes.as_ref().and_then(|schemes| {
-            schemes
-                .iter()
-                .filter(|s| url_matches_scheme(url, &s))
-                .next()
-                .map(|s| s.as_str())
-        })
+    /// Pick the response format to request, preferring JSON when this endpoint doesn't say
+    /// otherwise
+    pub(crate) fn preferred_format(&self) -> Format {
+        match &self.formats {
+            Some(formats)
+                if formats.iter().any(|f| f == "xml") && !formats.iter().any(|f| f == "json") =>
+            {
+                Format::Xml
+            }
+            _ => Format::Json,
+        }
+    }
+}
+
+/// Extract the host portion of a URL or scheme pattern, e.g. `www.youtube.com` from
+/// `https://www.youtube.com/watch*`, or `*.flickr.com` from `http://*.flickr.com/*`
+///
+/// Returns `None` for strings with no `scheme://` authority, such as custom URI schemes
+/// (`spotify:*`).
+fn scheme_host(s: &str) -> Option<&str> {
+    let after_scheme = s.split_once("://")?.1;
+    let authority_end = after_scheme
+        .find(['/', '?', '#'])
+        .unwrap_or(after_scheme.len());
+    let authority = &after_scheme[..authority_end];
+
+    // Drop userinfo (`user:pass@host`), if present.
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+
+    // Drop a trailing `:port`.
+    match authority.rfind(':') {
+        Some(idx) if authority[idx + 1..].bytes().all(|b| b.is_ascii_digit()) => {
+            Some(&authority[..idx])
+        }
+        _ => Some(authority),
     }
 }
 
@@ -146,6 +456,134 @@ fn url_matches_scheme(url: &str, scheme: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct FlakyHttp {
+        failures_remaining: Cell<u32>,
+    }
+
+    impl Http for FlakyHttp {
+        fn url_encode<'a>(&mut self, s: &'a str) -> HttpResult<Cow<'a, str>> {
+            Ok(s.into())
+        }
+
+        fn get(&mut self, _url: &str) -> HttpResult<String> {
+            if self.failures_remaining.get() > 0 {
+                self.failures_remaining.set(self.failures_remaining.get() - 1);
+                return Err("transient failure".into());
+            }
+
+            Ok("body".to_string())
+        }
+
+        fn is_retryable(&self, _err: &(dyn std::error::Error + 'static)) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn get_with_retry_succeeds_within_attempt_budget() {
+        let mut http = FlakyHttp {
+            failures_remaining: Cell::new(2),
+        };
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: std::time::Duration::ZERO,
+            treat_timeout_as_retryable: false,
+        };
+
+        assert_eq!(get_with_retry(&mut http, "url", &policy).unwrap(), "body");
+    }
+
+    #[test]
+    fn get_with_retry_gives_up_after_max_attempts() {
+        let mut http = FlakyHttp {
+            failures_remaining: Cell::new(5),
+        };
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            base_delay: std::time::Duration::ZERO,
+            treat_timeout_as_retryable: false,
+        };
+
+        assert!(get_with_retry(&mut http, "url", &policy).is_err());
+        assert_eq!(http.failures_remaining.get(), 3);
+    }
+
+    #[test]
+    fn append_query_params_skips_absent_params() {
+        let request = EmbedRequest::new("https://example.com/");
+        let mut query = "https://provider.example/oembed?format=json&url=x".to_string();
+
+        request.append_query_params(&mut query);
+
+        assert_eq!(query, "https://provider.example/oembed?format=json&url=x");
+    }
+
+    #[test]
+    fn append_query_params_appends_set_params() {
+        let request = EmbedRequest::new("https://example.com/")
+            .maxwidth(240)
+            .maxheight(160);
+        let mut query = "https://provider.example/oembed?format=json&url=x".to_string();
+
+        request.append_query_params(&mut query);
+
+        assert_eq!(
+            query,
+            "https://provider.example/oembed?format=json&url=x&maxwidth=240&maxheight=160"
+        );
+    }
+
+    fn provider_with_schemes(name: &str, schemes: &[&str]) -> Provider {
+        Provider {
+            name: name.to_string(),
+            url: format!("https://{}/", name),
+            endpoints: vec![Endpoint {
+                url: format!("https://{}/oembed", name),
+                schemes: Some(schemes.iter().map(|s| s.to_string()).collect()),
+                formats: None,
+                discovery: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn match_endpoint_uses_host_index() {
+        let schema = Schema::from_providers(vec![
+            provider_with_schemes("youtube", &["https://www.youtube.com/watch*"]),
+            provider_with_schemes("spotify", &["spotify:*"]),
+            provider_with_schemes("flickr", &["http://*.flickr.com/*"]),
+        ]);
+
+        let matched = schema
+            .match_endpoint("https://www.youtube.com/watch?v=5mMOsl8qpfc")
+            .unwrap();
+        assert_eq!(matched.provider.name, "youtube");
+
+        let matched = schema.match_endpoint("spotify:track:abc").unwrap();
+        assert_eq!(matched.provider.name, "spotify");
+
+        let matched = schema
+            .match_endpoint("http://farm4.flickr.com/photo/a.jpg")
+            .unwrap();
+        assert_eq!(matched.provider.name, "flickr");
+
+        assert!(schema.match_endpoint("https://example.com/").is_none());
+    }
+
+    #[test]
+    fn scheme_host_extracts_host() {
+        assert_eq!(
+            scheme_host("https://www.youtube.com/watch*"),
+            Some("www.youtube.com")
+        );
+        assert_eq!(scheme_host("http://*.flickr.com/*"), Some("*.flickr.com"));
+        assert_eq!(scheme_host("spotify:*"), None);
+        assert_eq!(scheme_host("https://example.com:8080/x"), Some("example.com"));
+    }
+
     #[test]
     fn url_matches_scheme() {
         assert_eq!(super::url_matches_scheme("spotify:abc", "spotify:*"), true);
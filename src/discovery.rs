@@ -0,0 +1,296 @@
+//! [Discovery](https://oembed.com/#section4) support.
+//!
+//! Discovery lets a consumer resolve an embed for a page that isn't served by any provider
+//! listed in [`Schema`]: the page itself is fetched and scanned for a `<link>` tag advertising
+//! its oEmbed endpoint.
+
+use crate::client::{Http, Schema};
+use crate::{Error, Response, Result};
+
+/// An oEmbed endpoint found via HTML discovery
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DiscoveredEndpoint {
+    /// The endpoint URL read from the page's discovery `<link>` tag
+    pub url: String,
+
+    /// The response format advertised by the discovery `<link>` tag
+    pub format: LinkFormat,
+}
+
+/// The format advertised by a discovery `<link>` tag
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LinkFormat {
+    /// `<link type="application/json+oembed">`
+    Json,
+
+    /// `<link type="text/xml+oembed">`
+    Xml,
+}
+
+impl Schema {
+    /// Resolve an embed for `page_url` via [Discovery](https://oembed.com/#section4)
+    ///
+    /// Fetches `page_url`, scans its HTML for an oEmbed discovery `<link>` tag, and fetches the
+    /// discovered endpoint. This is useful as a fallback when [`Schema::match_endpoint`] doesn't
+    /// find a known provider for a URL.
+    ///
+    /// The `href` of a discovery `<link>` is already a complete oEmbed request URL (it embeds
+    /// its own `url=`/`format=` query params pointing back at `page_url`), so it's fetched
+    /// directly rather than routed through [`Endpoint::fetch`][1], which would append a second,
+    /// conflicting query string on top.
+    ///
+    /// Returns `Ok(None)` if no discovery `<link>` was found. On success, returns the
+    /// [`DiscoveredEndpoint`] alongside the fetched response so callers can cache the endpoint
+    /// for future requests against the same page.
+    ///
+    /// [1]: crate::Endpoint::fetch
+    pub fn discover(
+        &self,
+        http: &mut impl Http,
+        page_url: &str,
+    ) -> Result<Option<(DiscoveredEndpoint, Response)>> {
+        let html = http.get(page_url).map_err(Error::HttpGet)?;
+
+        let discovered = match discover_from_html(&html) {
+            Some(discovered) => discovered,
+            None => return Ok(None),
+        };
+
+        let body = http.get(&discovered.url).map_err(Error::HttpGet)?;
+
+        let response = match discovered.format {
+            LinkFormat::Json => serde_json::from_str(&body).map_err(Error::ParseError)?,
+            LinkFormat::Xml => crate::xml::parse_response(&body)?,
+        };
+
+        Ok(Some((discovered, response)))
+    }
+}
+
+/// Scan `html` for an oEmbed discovery `<link>` tag
+///
+/// Recognizes both the JSON (`application/json+oembed`) and XML (`text/xml+oembed`) discovery
+/// link types described in section 4 of the oEmbed specification. This is a minimal, tolerant
+/// scanner: it looks for `<link ...>` tags and reads their `rel`/`type`/`href` attributes
+/// directly, rather than building a full DOM.
+pub fn discover_from_html(html: &str) -> Option<DiscoveredEndpoint> {
+    let lower = html.to_ascii_lowercase();
+    let mut search_from = 0;
+
+    while let Some(offset) = lower[search_from..].find("<link") {
+        let tag_start = search_from + offset;
+        let tag_end = match lower[tag_start..].find('>') {
+            Some(offset) => tag_start + offset,
+            None => break,
+        };
+        let tag = &html[tag_start..tag_end];
+
+        search_from = tag_end + 1;
+
+        let format = match (
+            find_attr(tag, "rel").as_deref(),
+            find_attr(tag, "type").as_deref(),
+        ) {
+            (Some("alternate"), Some("application/json+oembed")) => LinkFormat::Json,
+            (Some("alternate"), Some("text/xml+oembed")) => LinkFormat::Xml,
+            _ => continue,
+        };
+
+        if let Some(href) = find_attr(tag, "href") {
+            return Some(DiscoveredEndpoint {
+                url: decode_html_entities(&href),
+                format,
+            });
+        }
+    }
+
+    None
+}
+
+/// Read the value of attribute `name` from a single `<tag ...>` fragment
+fn find_attr(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let needle = format!("{}=", name);
+    let mut search_from = 0;
+
+    while let Some(offset) = lower[search_from..].find(&needle) {
+        let attr_start = search_from + offset;
+        let preceded_by_boundary = lower.as_bytes()[..attr_start]
+            .last()
+            .map(|b| b.is_ascii_whitespace())
+            .unwrap_or(true);
+
+        if !preceded_by_boundary {
+            search_from = attr_start + needle.len();
+            continue;
+        }
+
+        let value_start = attr_start + needle.len();
+        let quote = *tag.as_bytes().get(value_start)?;
+
+        return if quote == b'"' || quote == b'\'' {
+            let rest = &tag[value_start + 1..];
+            let end = rest.find(quote as char)?;
+            Some(rest[..end].to_string())
+        } else {
+            let rest = &tag[value_start..];
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            Some(rest[..end].to_string())
+        };
+    }
+
+    None
+}
+
+/// Decode the small set of HTML entities that commonly appear in `href` attributes
+///
+/// `&amp;` is decoded last so a doubly-escaped value like `&amp;lt;` round-trips to `&lt;`
+/// instead of being over-decoded into `<`.
+fn decode_html_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::HttpResult;
+    use crate::ResponseType;
+    use std::borrow::Cow;
+
+    struct FakeHttp {
+        requested: Vec<String>,
+    }
+
+    impl FakeHttp {
+        fn new() -> Self {
+            Self {
+                requested: Vec::new(),
+            }
+        }
+    }
+
+    impl Http for FakeHttp {
+        fn url_encode<'a>(&mut self, s: &'a str) -> HttpResult<Cow<'a, str>> {
+            Ok(s.into())
+        }
+
+        fn get(&mut self, url: &str) -> HttpResult<String> {
+            self.requested.push(url.to_string());
+
+            match url {
+                "https://page.example/" => Ok(concat!(
+                    r#"<link rel="alternate" type="application/json+oembed" "#,
+                    r#"href="https://example.com/oembed?url=foo&amp;format=json">"#
+                )
+                .to_string()),
+                "https://example.com/oembed?url=foo&format=json" => {
+                    Ok(r#"{"version":"1.0","type":"link"}"#.to_string())
+                }
+                other => panic!("unexpected request to {}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn discover_fetches_the_discovered_url_directly() {
+        let schema = Schema::default();
+        let mut http = FakeHttp::new();
+
+        let (discovered, response) = schema
+            .discover(&mut http, "https://page.example/")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            discovered.url,
+            "https://example.com/oembed?url=foo&format=json"
+        );
+        assert_eq!(response.response_type, ResponseType::Link);
+
+        // The discovered URL is already a complete oEmbed request; it must be fetched as-is,
+        // not routed back through `Endpoint::fetch` with an extra `?format=...&url=...` tacked
+        // on top.
+        assert_eq!(
+            http.requested,
+            vec![
+                "https://page.example/".to_string(),
+                "https://example.com/oembed?url=foo&format=json".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn discover_parses_xml_when_advertised() {
+        struct XmlHttp;
+
+        impl Http for XmlHttp {
+            fn url_encode<'a>(&mut self, s: &'a str) -> HttpResult<Cow<'a, str>> {
+                Ok(s.into())
+            }
+
+            fn get(&mut self, url: &str) -> HttpResult<String> {
+                match url {
+                    "https://page.example/" => Ok(
+                        r#"<link rel="alternate" type="text/xml+oembed" href="https://example.com/oembed.xml">"#
+                            .to_string(),
+                    ),
+                    "https://example.com/oembed.xml" => {
+                        Ok("<oembed><version>1.0</version><type>link</type></oembed>".to_string())
+                    }
+                    other => panic!("unexpected request to {}", other),
+                }
+            }
+        }
+
+        let schema = Schema::default();
+        let mut http = XmlHttp;
+
+        let (discovered, response) = schema
+            .discover(&mut http, "https://page.example/")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(discovered.format, LinkFormat::Xml);
+        assert_eq!(response.response_type, ResponseType::Link);
+    }
+
+    #[test]
+    fn discover_from_html_finds_json_link() {
+        let html = r#"
+            <html>
+                <head>
+                    <title>Some Page</title>
+                    <link rel="alternate" type="application/json+oembed"
+                          href="https://example.com/oembed?url=foo&amp;format=json" />
+                </head>
+                <body></body>
+            </html>
+        "#;
+
+        let discovered = discover_from_html(html).unwrap();
+
+        assert_eq!(discovered.format, LinkFormat::Json);
+        assert_eq!(discovered.url, "https://example.com/oembed?url=foo&format=json");
+    }
+
+    #[test]
+    fn discover_from_html_finds_xml_link() {
+        let html = r#"<link rel='alternate' type='text/xml+oembed' href='https://example.com/oembed.xml'>"#;
+
+        let discovered = discover_from_html(html).unwrap();
+
+        assert_eq!(discovered.format, LinkFormat::Xml);
+        assert_eq!(discovered.url, "https://example.com/oembed.xml");
+    }
+
+    #[test]
+    fn discover_from_html_ignores_unrelated_links() {
+        let html = r#"<link rel="stylesheet" type="text/css" href="style.css">"#;
+
+        assert!(discover_from_html(html).is_none());
+    }
+}
@@ -5,8 +5,6 @@
 //!
 //! * No HTTP client mechanism is included; users of this library must provide an
 //!   implementation of [`client::Http`] to functions requiring remote resources.
-//! * [Discovery](https://oembed.com/#section4) is not currently supported.
-//! * XML responses are not currently supported.
 //! * *Some endpoints* — not naming names — will return data that doesn't conform with the
 //!   specification; such data can't currently be parsed by this library. No decision
 //!   on how to address this has been made yet, and suggestions are welcome.
@@ -59,6 +57,12 @@ extern crate serde;
 extern crate serde_json;
 
 pub mod client;
+pub mod discovery;
+pub mod media;
+mod xml;
+
+#[cfg(feature = "async")]
+pub mod async_client;
 
 /// Crate-wide error type
 #[derive(Debug)]
@@ -69,8 +73,11 @@ pub enum Error {
     /// Returned if [`client::Http::get`] failed.
     HttpGet(Box<dyn std::error::Error>),
 
-    /// Returned if parsing a response failed.
+    /// Returned if parsing a JSON response failed.
     ParseError(serde_json::Error),
+
+    /// Returned if parsing an XML response failed.
+    XmlParseError(String),
 }
 
 impl std::fmt::Display for Error {
@@ -100,7 +107,9 @@ pub struct Endpoint {
     pub schemes: Option<Vec<String>>,
     pub formats: Option<Vec<String>>,
 
-    /// Not currently supported
+    /// The `discovery` flag given for this endpoint in the provider schema. This crate does
+    /// not read it; endpoints found via [Discovery](https://oembed.com/#section4) are returned
+    /// as [`discovery::DiscoveredEndpoint`] instead. See [`crate::discovery`].
     pub discovery: Option<bool>,
 }
 
@@ -125,6 +134,28 @@ pub struct Response {
     pub thumbnail_height: Option<i32>,
 }
 
+/// Response serialization format
+///
+/// See section 2.1 of the [oEmbed specification][1]; `json` is mandatory for providers to
+/// support, `xml` is optional.
+///
+/// [1]: https://oembed.com/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Format {
+    Json,
+    Xml,
+}
+
+impl Format {
+    /// The value of the oEmbed `format` request parameter for this format
+    pub fn as_query_param(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Xml => "xml",
+        }
+    }
+}
+
 /// Type-specific oEmbed response data
 ///
 /// See section 2.3.4 of the [oEmbed specification][1].
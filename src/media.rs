@@ -0,0 +1,154 @@
+//! Normalized media extraction across `photo`/`video`/`rich` responses
+//!
+//! Consumers often just want "a displayable image plus a source link" regardless of which
+//! [`ResponseType`] variant a provider returned. [`Response::media`] gives a single uniform shape
+//! for that instead of matching on every variant.
+
+use crate::{Response, ResponseType};
+
+/// A normalized view of a [`Response`], uniform across `photo`/`video`/`rich` types
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Media {
+    /// A displayable image: the `photo` URL, or `thumbnail_url` if that wasn't available
+    pub image_url: Option<String>,
+
+    /// Embeddable HTML, for `video`/`rich` responses
+    pub embed_html: Option<String>,
+
+    /// Width of `image_url` or `embed_html`, in pixels
+    pub width: Option<i32>,
+
+    /// Height of `image_url` or `embed_html`, in pixels
+    pub height: Option<i32>,
+
+    /// Lowercased file extension inferred from `image_url`'s path (e.g. `"jpg"`)
+    pub file_type: Option<String>,
+
+    /// A link back to the source: the author's page, falling back to the provider's
+    pub source_url: Option<String>,
+}
+
+impl Response {
+    /// Build a normalized [`Media`] view of this response
+    ///
+    /// Uses the `photo`/`video`/`rich` type-specific fields when present, falling back to
+    /// `thumbnail_url`/`thumbnail_width`/`thumbnail_height` for the image when they aren't (e.g.
+    /// a `link` response, or a `video`/`rich` response that also has a thumbnail).
+    pub fn media(&self) -> Media {
+        let (mut image_url, embed_html, mut width, mut height) = match &self.response_type {
+            ResponseType::Photo { url, width, height } => (Some(url.clone()), None, *width, *height),
+            ResponseType::Video { html, width, height } => (None, Some(html.clone()), *width, *height),
+            ResponseType::Rich { html, width, height } => (None, Some(html.clone()), *width, *height),
+            ResponseType::Link => (None, None, None, None),
+        };
+
+        if image_url.is_none() {
+            image_url = self.thumbnail_url.clone();
+            width = width.or(self.thumbnail_width);
+            height = height.or(self.thumbnail_height);
+        }
+
+        let file_type = image_url.as_deref().and_then(file_extension);
+
+        Media {
+            image_url,
+            embed_html,
+            width,
+            height,
+            file_type,
+            source_url: self.author_url.clone().or_else(|| self.provider_url.clone()),
+        }
+    }
+}
+
+/// Infer a lowercased file extension from a URL's path, ignoring any query string or fragment
+fn file_extension(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let path = match path.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |idx| &rest[idx..]),
+        None => path,
+    };
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+
+    let (_, ext) = file_name.rsplit_once('.')?;
+    if ext.is_empty() {
+        return None;
+    }
+
+    Some(ext.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn media_from_photo_response() {
+        let response = Response {
+            response_type: ResponseType::Photo {
+                url: "http://farm4.static.flickr.com/3123/2341623661_7c99f48bbf_m.JPG?x=1"
+                    .to_string(),
+                width: Some(240),
+                height: Some(160),
+            },
+            version: "1.0".to_string(),
+            title: None,
+            author_name: None,
+            author_url: Some("http://www.flickr.com/photos/bees/".to_string()),
+            provider_name: None,
+            provider_url: Some("http://www.flickr.com/".to_string()),
+            cache_age: None,
+            thumbnail_url: None,
+            thumbnail_width: None,
+            thumbnail_height: None,
+        };
+
+        let media = response.media();
+
+        assert_eq!(
+            media.image_url.as_deref(),
+            Some("http://farm4.static.flickr.com/3123/2341623661_7c99f48bbf_m.JPG?x=1")
+        );
+        assert_eq!(media.file_type.as_deref(), Some("jpg"));
+        assert_eq!(media.width, Some(240));
+        assert_eq!(media.source_url.as_deref(), Some("http://www.flickr.com/photos/bees/"));
+    }
+
+    #[test]
+    fn media_from_rich_response_falls_back_to_thumbnail() {
+        let response = Response {
+            response_type: ResponseType::Rich {
+                html: "<iframe></iframe>".to_string(),
+                width: Some(400),
+                height: Some(300),
+            },
+            version: "1.0".to_string(),
+            title: None,
+            author_name: None,
+            author_url: None,
+            provider_name: None,
+            provider_url: Some("http://example.com/".to_string()),
+            cache_age: None,
+            thumbnail_url: Some("http://example.com/thumb.png".to_string()),
+            thumbnail_width: Some(100),
+            thumbnail_height: Some(75),
+        };
+
+        let media = response.media();
+
+        assert_eq!(media.embed_html.as_deref(), Some("<iframe></iframe>"));
+        assert_eq!(media.image_url.as_deref(), Some("http://example.com/thumb.png"));
+        assert_eq!(media.file_type.as_deref(), Some("png"));
+        assert_eq!(media.width, Some(400));
+        assert_eq!(media.source_url.as_deref(), Some("http://example.com/"));
+    }
+
+    #[test]
+    fn file_extension_ignores_query_string() {
+        assert_eq!(
+            file_extension("http://example.com/a/b.JPG?w=100"),
+            Some("jpg".to_string())
+        );
+        assert_eq!(file_extension("http://example.com/no-extension"), None);
+    }
+}
@@ -0,0 +1,157 @@
+//! A minimal, hand-rolled reader for `<oembed>` XML responses (section 2.3.4 of the oEmbed
+//! specification). It maps known element names directly onto [`Response`] fields rather than
+//! building a full DOM.
+
+use crate::{Error, Response, ResponseType, Result};
+use std::collections::HashMap;
+
+/// Parse an `<oembed>` XML document into a [`Response`]
+pub(crate) fn parse_response(xml: &str) -> Result<Response> {
+    let body = element_body(xml, "oembed")
+        .ok_or_else(|| Error::XmlParseError("missing <oembed> root element".to_string()))?;
+
+    let fields: HashMap<String, String> = iter_elements(body)
+        .into_iter()
+        .map(|(name, value)| (name, decode_text(&value)))
+        .collect();
+
+    let get = |k: &str| fields.get(k).cloned();
+    let get_i32 = |k: &str| get(k).and_then(|v| v.parse().ok());
+
+    let response_type = match get("type").as_deref() {
+        Some("photo") => ResponseType::Photo {
+            url: get("url")
+                .ok_or_else(|| Error::XmlParseError("photo response missing <url>".to_string()))?,
+            width: get_i32("width"),
+            height: get_i32("height"),
+        },
+
+        Some("video") => ResponseType::Video {
+            html: get("html")
+                .ok_or_else(|| Error::XmlParseError("video response missing <html>".to_string()))?,
+            width: get_i32("width"),
+            height: get_i32("height"),
+        },
+
+        Some("rich") => ResponseType::Rich {
+            html: get("html")
+                .ok_or_else(|| Error::XmlParseError("rich response missing <html>".to_string()))?,
+            width: get_i32("width"),
+            height: get_i32("height"),
+        },
+
+        Some("link") => ResponseType::Link,
+
+        other => {
+            return Err(Error::XmlParseError(format!(
+                "unknown or missing oEmbed type: {:?}",
+                other
+            )))
+        }
+    };
+
+    Ok(Response {
+        response_type,
+        version: get("version").unwrap_or_default(),
+        title: get("title"),
+        author_name: get("author_name"),
+        author_url: get("author_url"),
+        provider_name: get("provider_name"),
+        provider_url: get("provider_url"),
+        cache_age: get("cache_age"),
+        thumbnail_url: get("thumbnail_url"),
+        thumbnail_width: get_i32("thumbnail_width"),
+        thumbnail_height: get_i32("thumbnail_height"),
+    })
+}
+
+/// Return the inner text of the first `<name>...</name>` element found in `xml`
+fn element_body<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let open_start = xml.find(&format!("<{}", name))?;
+    let open_end = xml[open_start..].find('>')? + open_start + 1;
+    let close_tag = format!("</{}>", name);
+    let close_start = xml[open_end..].find(&close_tag)? + open_end;
+
+    Some(&xml[open_end..close_start])
+}
+
+/// Iterate over the direct child elements of an XML fragment as `(name, inner_text)` pairs
+fn iter_elements(xml: &str) -> Vec<(String, String)> {
+    let mut elements = Vec::new();
+    let mut rest = xml;
+
+    while let Some(offset) = rest.find('<') {
+        rest = &rest[offset..];
+
+        if rest.starts_with("</") {
+            break;
+        }
+
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[1..name_end].to_string();
+
+        let body = match element_body(rest, &name) {
+            Some(body) => body,
+            None => break,
+        };
+        elements.push((name.clone(), body.to_string()));
+
+        let close_tag = format!("</{}>", name);
+        match rest.find(&close_tag) {
+            Some(close_pos) => rest = &rest[close_pos + close_tag.len()..],
+            None => break,
+        }
+    }
+
+    elements
+}
+
+/// Trim whitespace and decode the handful of XML entities likely to appear in element text
+fn decode_text(s: &str) -> String {
+    s.trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_response_reads_photo() {
+        let xml = r#"
+            <oembed>
+                <version>1.0</version>
+                <type>photo</type>
+                <width>240</width>
+                <height>160</height>
+                <title>ZB8T0193</title>
+                <url>http://farm4.static.flickr.com/3123/2341623661_7c99f48bbf_m.jpg</url>
+                <author_name>Bees</author_name>
+                <provider_name>Flickr</provider_name>
+            </oembed>
+        "#;
+
+        let response = parse_response(xml).unwrap();
+
+        assert_eq!(response.title.as_deref(), Some("ZB8T0193"));
+        match response.response_type {
+            ResponseType::Photo { url, width, height } => {
+                assert_eq!(url, "http://farm4.static.flickr.com/3123/2341623661_7c99f48bbf_m.jpg");
+                assert_eq!(width, Some(240));
+                assert_eq!(height, Some(160));
+            }
+            other => panic!("expected Photo, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_response_rejects_missing_root() {
+        assert!(parse_response("<not-oembed></not-oembed>").is_err());
+    }
+}